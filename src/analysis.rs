@@ -0,0 +1,132 @@
+//! Objective statistics about a map, computed purely from its hit objects and timing data.
+
+use crate::Qua;
+
+/// Default jack threshold in milliseconds: consecutive same-lane notes closer together than this count as a jack
+pub const DEFAULT_JACK_THRESHOLD_MS: i32 = 120;
+
+/// Objective statistics about a map, computed purely from its hit objects, timing points, and game mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapAnalysis {
+    /// Length of the map in milliseconds, from the first to the last hit object
+    pub length: f32,
+    /// Average notes per second across the whole map
+    pub notes_per_second: f32,
+    /// The highest notes-per-second over any sliding 1 second window
+    pub peak_notes_per_second: f32,
+    /// Fraction of hit objects that are long notes (0.0 to 1.0)
+    pub long_note_percent: f32,
+    /// Number of notes in each lane, indexed from lane 1 at index 0
+    pub lane_counts: Vec<i32>,
+    /// Number of same-lane note pairs closer together than the jack threshold
+    pub jack_count: i32,
+    /// Number of notes in the lower half of lanes (left hand)
+    pub left_hand_notes: i32,
+    /// Number of notes in the upper half of lanes (right hand)
+    pub right_hand_notes: i32,
+    /// The BPM of the timing point covering the most total time
+    pub dominant_bpm: f32,
+}
+
+impl Qua {
+    /// Compute objective statistics about this map from its hit objects, timing points, and game mode.
+    ///
+    /// Uses [`DEFAULT_JACK_THRESHOLD_MS`] as the jack threshold. See
+    /// [`Qua::analyze_with_jack_threshold`] to use a different threshold.
+    pub fn analyze(&self) -> MapAnalysis {
+        self.analyze_with_jack_threshold(DEFAULT_JACK_THRESHOLD_MS)
+    }
+
+    /// Like [`Qua::analyze`], but with a configurable jack threshold in milliseconds.
+    pub fn analyze_with_jack_threshold(&self, jack_threshold_ms: i32) -> MapAnalysis {
+        let key_count = self.game_mode.clone().get_key_count();
+
+        let first_time = self.hit_objects.iter().map(|h| h.start_time).min().unwrap_or(0);
+        let last_time = self
+            .hit_objects
+            .iter()
+            .map(|h| h.end_time.max(h.start_time))
+            .max()
+            .unwrap_or(0);
+        let length = (last_time - first_time) as f32;
+
+        let note_count = self.hit_objects.len();
+        let long_note_count = self.hit_objects.iter().filter(|h| h.end_time > 0).count();
+        let long_note_percent = if note_count > 0 {
+            long_note_count as f32 / note_count as f32
+        } else {
+            0.0
+        };
+
+        let notes_per_second = if length > 0.0 {
+            note_count as f32 / (length / 1000.0)
+        } else {
+            0.0
+        };
+
+        let mut start_times: Vec<i32> = self.hit_objects.iter().map(|h| h.start_time).collect();
+        start_times.sort_unstable();
+        let peak_notes_per_second = peak_density(&start_times);
+
+        let mut lane_counts = vec![0; key_count as usize];
+        let mut left_hand_notes = 0;
+        let mut right_hand_notes = 0;
+        let left_hand_lanes = (key_count + 1) / 2;
+        for obj in &self.hit_objects {
+            if obj.lane >= 1 && (obj.lane as usize) <= lane_counts.len() {
+                lane_counts[(obj.lane - 1) as usize] += 1;
+            }
+
+            if obj.lane <= left_hand_lanes {
+                left_hand_notes += 1;
+            } else {
+                right_hand_notes += 1;
+            }
+        }
+
+        let mut jack_count = 0;
+        for lane in 1..=key_count {
+            let mut lane_times: Vec<i32> = self
+                .hit_objects
+                .iter()
+                .filter(|h| h.lane == lane)
+                .map(|h| h.start_time)
+                .collect();
+            lane_times.sort_unstable();
+            jack_count += lane_times
+                .windows(2)
+                .filter(|w| w[1] - w[0] < jack_threshold_ms)
+                .count() as i32;
+        }
+
+        MapAnalysis {
+            length,
+            notes_per_second,
+            peak_notes_per_second,
+            long_note_percent,
+            lane_counts,
+            jack_count,
+            left_hand_notes,
+            right_hand_notes,
+            dominant_bpm: self.base_bpm(),
+        }
+    }
+}
+
+/// Highest number of note onsets within any sliding 1 second window, given sorted start times
+fn peak_density(sorted_start_times: &[i32]) -> f32 {
+    if sorted_start_times.is_empty() {
+        return 0.0;
+    }
+
+    let mut peak = 0;
+    let mut left = 0;
+    for right in 0..sorted_start_times.len() {
+        while sorted_start_times[right] - sorted_start_times[left] > 1000 {
+            left += 1;
+        }
+        peak = peak.max(right - left + 1);
+    }
+
+    peak as f32
+}
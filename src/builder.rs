@@ -0,0 +1,326 @@
+//! Fluent builders for [`Qua`] and its sub-structs, validating invariants on `build()`
+//! instead of letting callers assemble invalid maps through struct literals.
+
+use crate::{
+    CustomAudioSampleInfo, EditorLayerInfo, GameMode, HitObjectInfo, HitSounds, KeySoundInfo, Qua,
+    ScrollVelocityInfo, SoundEffectInfo, TimeSignature, TimingPointInfo,
+};
+
+/// Error returned by a builder's `build()` when the assembled value would be invalid
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    /// A time field was negative
+    NegativeTime,
+    /// `end_time` was nonzero and before `start_time`
+    EndTimeBeforeStartTime,
+    /// A hit object's lane was outside the range the game mode (and scratch key) allow
+    LaneOutOfRange { lane: i32, max_lane: i32 },
+    /// A `Qua` was built with no timing points
+    MissingTimingPoints,
+}
+
+/// Builder for [`Qua`]
+#[derive(Debug, Clone, Default)]
+pub struct QuaBuilder {
+    qua: Qua,
+}
+
+impl QuaBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn audio_file(mut self, audio_file: impl Into<String>) -> Self {
+        self.qua.audio_file = audio_file.into();
+        self
+    }
+
+    pub fn song_preview_time(mut self, song_preview_time: i32) -> Self {
+        self.qua.song_preview_time = song_preview_time;
+        self
+    }
+
+    pub fn background_file(mut self, background_file: impl Into<String>) -> Self {
+        self.qua.background_file = background_file.into();
+        self
+    }
+
+    pub fn banner_file(mut self, banner_file: impl Into<String>) -> Self {
+        self.qua.banner_file = banner_file.into();
+        self
+    }
+
+    pub fn map_id(mut self, map_id: i32) -> Self {
+        self.qua.map_id = map_id;
+        self
+    }
+
+    pub fn map_set_id(mut self, map_set_id: i32) -> Self {
+        self.qua.map_set_id = map_set_id;
+        self
+    }
+
+    pub fn mode(mut self, game_mode: GameMode) -> Self {
+        self.qua.game_mode = game_mode;
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.qua.title = title.into();
+        self
+    }
+
+    pub fn artist(mut self, artist: impl Into<String>) -> Self {
+        self.qua.artist = artist.into();
+        self
+    }
+
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.qua.source = source.into();
+        self
+    }
+
+    pub fn tags(mut self, tags: impl Into<String>) -> Self {
+        self.qua.tags = tags.into();
+        self
+    }
+
+    pub fn creator(mut self, creator: impl Into<String>) -> Self {
+        self.qua.creator = creator.into();
+        self
+    }
+
+    pub fn difficulty_name(mut self, difficulty_name: impl Into<String>) -> Self {
+        self.qua.difficulty_name = difficulty_name.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.qua.description = description.into();
+        self
+    }
+
+    pub fn genre(mut self, genre: impl Into<String>) -> Self {
+        self.qua.genre = genre.into();
+        self
+    }
+
+    pub fn bpm_does_not_affect_scroll_velocity(mut self, value: bool) -> Self {
+        self.qua.bpm_does_not_affect_scroll_velocity = value;
+        self
+    }
+
+    pub fn initial_scroll_velocity(mut self, value: f32) -> Self {
+        self.qua.initial_scroll_velocity = value;
+        self
+    }
+
+    pub fn has_scratch_key(mut self, value: bool) -> Self {
+        self.qua.has_scratch_key = value;
+        self
+    }
+
+    pub fn editor_layer(mut self, layer: EditorLayerInfo) -> Self {
+        self.qua.editor_layers.push(layer);
+        self
+    }
+
+    pub fn custom_audio_sample(mut self, sample: CustomAudioSampleInfo) -> Self {
+        self.qua.custom_audio_samples.push(sample);
+        self
+    }
+
+    pub fn sound_effect(mut self, sound_effect: SoundEffectInfo) -> Self {
+        self.qua.sound_effects.push(sound_effect);
+        self
+    }
+
+    pub fn timing_point(mut self, timing_point: TimingPointInfo) -> Self {
+        self.qua.timing_points.push(timing_point);
+        self
+    }
+
+    pub fn slider_velocity(mut self, slider_velocity: ScrollVelocityInfo) -> Self {
+        self.qua.slider_velocities.push(slider_velocity);
+        self
+    }
+
+    pub fn hit_object(mut self, hit_object: HitObjectInfo) -> Self {
+        self.qua.hit_objects.push(hit_object);
+        self
+    }
+
+    /// Validate and build the [`Qua`]
+    ///
+    /// Checks that there is at least one timing point, that the start times of timing points
+    /// and scroll velocities and the start and end times of hit objects are non-negative
+    /// (sound effect times and `song_preview_time` are not checked), that every hit object's `end_time` is either 0 or at or after its `start_time`, and
+    /// that every hit object's lane is within the game mode's key count (plus the scratch
+    /// key, if enabled).
+    pub fn build(self) -> Result<Qua, BuildError> {
+        let qua = self.qua;
+
+        if qua.timing_points.is_empty() {
+            return Err(BuildError::MissingTimingPoints);
+        }
+
+        for tp in &qua.timing_points {
+            if tp.start_time < 0.0 {
+                return Err(BuildError::NegativeTime);
+            }
+        }
+
+        for sv in &qua.slider_velocities {
+            if sv.start_time < 0 {
+                return Err(BuildError::NegativeTime);
+            }
+        }
+
+        let max_lane = qua.game_mode.clone().get_key_count() + if qua.has_scratch_key { 1 } else { 0 };
+
+        for obj in &qua.hit_objects {
+            if obj.start_time < 0 || obj.end_time < 0 {
+                return Err(BuildError::NegativeTime);
+            }
+            if obj.end_time != 0 && obj.end_time < obj.start_time {
+                return Err(BuildError::EndTimeBeforeStartTime);
+            }
+            if obj.lane < 1 || obj.lane > max_lane {
+                return Err(BuildError::LaneOutOfRange {
+                    lane: obj.lane,
+                    max_lane,
+                });
+            }
+        }
+
+        Ok(qua)
+    }
+}
+
+/// Builder for [`TimingPointInfo`]
+#[derive(Debug, Clone, Default)]
+pub struct TimingPointBuilder {
+    timing_point: TimingPointInfo,
+}
+
+impl TimingPointBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start_time(mut self, start_time: f32) -> Self {
+        self.timing_point.start_time = start_time;
+        self
+    }
+
+    pub fn bpm(mut self, bpm: f32) -> Self {
+        self.timing_point.bpm = bpm;
+        self
+    }
+
+    pub fn signature(mut self, signature: TimeSignature) -> Self {
+        self.timing_point.signature = signature;
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.timing_point.hidden = hidden;
+        self
+    }
+
+    pub fn build(self) -> Result<TimingPointInfo, BuildError> {
+        if self.timing_point.start_time < 0.0 {
+            return Err(BuildError::NegativeTime);
+        }
+
+        Ok(self.timing_point)
+    }
+}
+
+/// Builder for [`ScrollVelocityInfo`]
+#[derive(Debug, Clone, Default)]
+pub struct ScrollVelocityInfoBuilder {
+    slider_velocity: ScrollVelocityInfo,
+}
+
+impl ScrollVelocityInfoBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start_time(mut self, start_time: i32) -> Self {
+        self.slider_velocity.start_time = start_time;
+        self
+    }
+
+    pub fn multiplier(mut self, multiplier: f32) -> Self {
+        self.slider_velocity.multiplier = multiplier;
+        self
+    }
+
+    pub fn build(self) -> Result<ScrollVelocityInfo, BuildError> {
+        if self.slider_velocity.start_time < 0 {
+            return Err(BuildError::NegativeTime);
+        }
+
+        Ok(self.slider_velocity)
+    }
+}
+
+/// Builder for [`HitObjectInfo`]
+///
+/// Lane bounds aren't validated here since they depend on the containing [`Qua`]'s game mode;
+/// [`QuaBuilder::build`] validates them once the hit object is attached to a map.
+#[derive(Debug, Clone, Default)]
+pub struct HitObjectInfoBuilder {
+    hit_object: HitObjectInfo,
+}
+
+impl HitObjectInfoBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start_time(mut self, start_time: i32) -> Self {
+        self.hit_object.start_time = start_time;
+        self
+    }
+
+    pub fn lane(mut self, lane: i32) -> Self {
+        self.hit_object.lane = lane;
+        self
+    }
+
+    pub fn end_time(mut self, end_time: i32) -> Self {
+        self.hit_object.end_time = end_time;
+        self
+    }
+
+    pub fn hit_sound(mut self, hit_sound: HitSounds) -> Self {
+        self.hit_object.hit_sound = hit_sound;
+        self
+    }
+
+    pub fn key_sound(mut self, key_sound: KeySoundInfo) -> Self {
+        self.hit_object.key_sounds.push(key_sound);
+        self
+    }
+
+    pub fn editor_layer(mut self, editor_layer: i32) -> Self {
+        self.hit_object.editor_layer = editor_layer;
+        self
+    }
+
+    pub fn build(self) -> Result<HitObjectInfo, BuildError> {
+        let hit_object = self.hit_object;
+
+        if hit_object.start_time < 0 || hit_object.end_time < 0 {
+            return Err(BuildError::NegativeTime);
+        }
+        if hit_object.end_time != 0 && hit_object.end_time < hit_object.start_time {
+            return Err(BuildError::EndTimeBeforeStartTime);
+        }
+
+        Ok(hit_object)
+    }
+}
@@ -0,0 +1,325 @@
+//! Convert between osu!mania `.osu` beatmaps and Quaver `.qua` maps.
+//!
+//! This does not aim to be a full osu! beatmap parser; it only reads the
+//! sections needed to round-trip a mania map into a [`Qua`] and back.
+
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
+
+use crate::{GameMode, HitObjectInfo, Qua, ScrollVelocityInfo, TimeSignature, TimingPointInfo};
+
+/// Error while converting between osu! and Quaver map formats
+#[derive(Debug)]
+pub enum ConvertError {
+    IoError(std::io::Error),
+    ParseError(String),
+}
+
+/// A parsed osu!mania beatmap, containing only the fields needed to convert to/from [`Qua`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OsuBeatmap {
+    pub audio_filename: String,
+    pub title: String,
+    pub artist: String,
+    pub creator: String,
+    pub version: String,
+    /// `CircleSize` in mania maps is the key count
+    pub circle_size: f32,
+    pub timing_points: Vec<OsuTimingPoint>,
+    pub hit_objects: Vec<OsuHitObject>,
+}
+
+/// A single `[TimingPoints]` line, either a BPM change (uninherited) or an SV change (inherited)
+#[derive(Debug, Clone, PartialEq)]
+pub struct OsuTimingPoint {
+    pub time: f32,
+    pub beat_length: f32,
+    pub uninherited: bool,
+}
+
+/// A single `[HitObjects]` line
+#[derive(Debug, Clone, PartialEq)]
+pub struct OsuHitObject {
+    pub x: i32,
+    pub time: i32,
+    pub hit_sound: u8,
+    pub is_hold: bool,
+    /// Only meaningful if `is_hold` is true
+    pub end_time: i32,
+}
+
+impl OsuBeatmap {
+    /// Parse an osu!mania `.osu` file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<OsuBeatmap, ConvertError> {
+        let mut file = File::open(path).map_err(ConvertError::IoError)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .map_err(ConvertError::IoError)?;
+        OsuBeatmap::parse(&contents)
+    }
+
+    /// Parse an osu!mania `.osu` beatmap from its raw text contents
+    pub fn parse(s: &str) -> Result<OsuBeatmap, ConvertError> {
+        let sections = split_sections(s);
+
+        let general = key_values(sections.get("General").map(Vec::as_slice).unwrap_or(&[]));
+        let metadata = key_values(sections.get("Metadata").map(Vec::as_slice).unwrap_or(&[]));
+        let difficulty = key_values(sections.get("Difficulty").map(Vec::as_slice).unwrap_or(&[]));
+
+        let audio_filename = general.get("AudioFilename").cloned().unwrap_or_default();
+        let title = metadata.get("Title").cloned().unwrap_or_default();
+        let artist = metadata.get("Artist").cloned().unwrap_or_default();
+        let creator = metadata.get("Creator").cloned().unwrap_or_default();
+        let version = metadata.get("Version").cloned().unwrap_or_default();
+
+        let circle_size = difficulty
+            .get("CircleSize")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(4.0);
+
+        let timing_points = match sections.get("TimingPoints") {
+            Some(lines) => parse_timing_points(lines)?,
+            None => Vec::new(),
+        };
+
+        let hit_objects = match sections.get("HitObjects") {
+            Some(lines) => parse_hit_objects(lines)?,
+            None => Vec::new(),
+        };
+
+        Ok(OsuBeatmap {
+            audio_filename,
+            title,
+            artist,
+            creator,
+            version,
+            circle_size,
+            timing_points,
+            hit_objects,
+        })
+    }
+
+    /// Convert this beatmap into a [`Qua`]
+    pub fn to_qua(&self) -> Result<Qua, ConvertError> {
+        let key_count = self.circle_size.round() as i32;
+        let game_mode = GameMode::from_key_count(key_count).ok_or_else(|| {
+            ConvertError::ParseError(format!("unsupported key count {}", key_count))
+        })?;
+
+        let mut timing_points = Vec::new();
+        let mut slider_velocities = Vec::new();
+
+        for tp in &self.timing_points {
+            if tp.uninherited {
+                timing_points.push(TimingPointInfo {
+                    start_time: tp.time,
+                    bpm: 60_000.0 / tp.beat_length,
+                    signature: TimeSignature::Quadruple,
+                    hidden: false,
+                });
+            } else {
+                slider_velocities.push(ScrollVelocityInfo {
+                    start_time: tp.time as i32,
+                    multiplier: 100.0 / -tp.beat_length,
+                });
+            }
+        }
+
+        let hit_objects = self
+            .hit_objects
+            .iter()
+            .map(|obj| HitObjectInfo {
+                start_time: obj.time,
+                lane: obj.x * key_count / 512 + 1,
+                end_time: if obj.is_hold { obj.end_time } else { 0 },
+                hit_sound: obj.hit_sound.into(),
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(Qua {
+            audio_file: self.audio_filename.clone(),
+            title: self.title.clone(),
+            artist: self.artist.clone(),
+            creator: self.creator.clone(),
+            difficulty_name: self.version.clone(),
+            game_mode,
+            timing_points,
+            slider_velocities,
+            hit_objects,
+            ..Default::default()
+        })
+    }
+}
+
+impl Qua {
+    /// Render this map as an osu!mania `.osu` beatmap
+    pub fn to_osu(&self) -> String {
+        let key_count = self.game_mode.clone().get_key_count();
+        let mut s = String::new();
+
+        s.push_str("osu file format v14\n\n");
+
+        s.push_str("[General]\n");
+        s.push_str(&format!("AudioFilename: {}\n", self.audio_file));
+        s.push_str("Mode: 3\n\n");
+
+        s.push_str("[Metadata]\n");
+        s.push_str(&format!("Title:{}\n", self.title));
+        s.push_str(&format!("Artist:{}\n", self.artist));
+        s.push_str(&format!("Creator:{}\n", self.creator));
+        s.push_str(&format!("Version:{}\n\n", self.difficulty_name));
+
+        s.push_str("[Difficulty]\n");
+        s.push_str(&format!("CircleSize:{}\n\n", key_count));
+
+        s.push_str("[TimingPoints]\n");
+        let mut points: Vec<(f32, f32, bool)> = self
+            .timing_points
+            .iter()
+            .map(|tp| (tp.start_time, 60_000.0 / tp.bpm, true))
+            .collect();
+        points.extend(
+            self.slider_velocities
+                .iter()
+                .map(|sv| (sv.start_time as f32, -100.0 / sv.multiplier, false)),
+        );
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        for (time, beat_length, uninherited) in points {
+            s.push_str(&format!(
+                "{},{},4,0,0,0,{},0\n",
+                time,
+                beat_length,
+                if uninherited { 1 } else { 0 }
+            ));
+        }
+        s.push('\n');
+
+        s.push_str("[HitObjects]\n");
+        for obj in &self.hit_objects {
+            let lane_width = 512 / key_count;
+            let x = (obj.lane - 1) * lane_width + lane_width / 2;
+            let hit_sound: u8 = obj.hit_sound.into();
+            if obj.end_time > 0 {
+                s.push_str(&format!(
+                    "{},192,{},128,{},{}:0:0:0:0:\n",
+                    x, obj.start_time, hit_sound, obj.end_time
+                ));
+            } else {
+                s.push_str(&format!(
+                    "{},192,{},1,{},0:0:0:0:\n",
+                    x, obj.start_time, hit_sound
+                ));
+            }
+        }
+
+        s
+    }
+}
+
+/// Split an `.osu` file into its `[Section]` bodies, keyed by section name without brackets
+fn split_sections(s: &str) -> HashMap<String, Vec<String>> {
+    let mut sections: HashMap<String, Vec<String>> = HashMap::new();
+    let mut current = String::new();
+
+    for line in s.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            current = line[1..line.len() - 1].to_string();
+            sections.entry(current.clone()).or_default();
+            continue;
+        }
+        if !current.is_empty() {
+            sections.entry(current.clone()).or_default().push(line.to_string());
+        }
+    }
+
+    sections
+}
+
+/// Parse `Key: Value` / `Key:Value` lines from a section into a lookup table
+fn key_values(lines: &[String]) -> HashMap<String, String> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn parse_timing_points(lines: &[String]) -> Result<Vec<OsuTimingPoint>, ConvertError> {
+    lines
+        .iter()
+        .map(|line| {
+            let parts: Vec<&str> = line.split(',').collect();
+            if parts.len() < 7 {
+                return Err(ConvertError::ParseError(format!(
+                    "invalid timing point line: {}",
+                    line
+                )));
+            }
+            let time: f32 = parts[0]
+                .parse()
+                .map_err(|_| ConvertError::ParseError(line.clone()))?;
+            let beat_length: f32 = parts[1]
+                .parse()
+                .map_err(|_| ConvertError::ParseError(line.clone()))?;
+            let uninherited = parts[6].trim() == "1";
+
+            Ok(OsuTimingPoint {
+                time,
+                beat_length,
+                uninherited,
+            })
+        })
+        .collect()
+}
+
+fn parse_hit_objects(lines: &[String]) -> Result<Vec<OsuHitObject>, ConvertError> {
+    lines
+        .iter()
+        .map(|line| {
+            let parts: Vec<&str> = line.split(',').collect();
+            if parts.len() < 5 {
+                return Err(ConvertError::ParseError(format!(
+                    "invalid hit object line: {}",
+                    line
+                )));
+            }
+            let x: i32 = parts[0]
+                .parse()
+                .map_err(|_| ConvertError::ParseError(line.clone()))?;
+            let time: i32 = parts[2]
+                .parse()
+                .map_err(|_| ConvertError::ParseError(line.clone()))?;
+            let object_type: u8 = parts[3]
+                .parse()
+                .map_err(|_| ConvertError::ParseError(line.clone()))?;
+            let hit_sound: u8 = parts[4]
+                .parse()
+                .map_err(|_| ConvertError::ParseError(line.clone()))?;
+            let is_hold = object_type & (1 << 7) != 0;
+
+            let end_time = if is_hold {
+                parts
+                    .get(5)
+                    .and_then(|params| params.split(':').next())
+                    .and_then(|t| t.parse().ok())
+                    .ok_or_else(|| ConvertError::ParseError(format!("missing endTime in: {}", line)))?
+            } else {
+                0
+            };
+
+            Ok(OsuHitObject {
+                x,
+                time,
+                hit_sound,
+                is_hold,
+                end_time,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,49 @@
+//! Typed bitflags for [`HitObjectInfo::hit_sound`](crate::HitObjectInfo::hit_sound)
+
+use bitflags::bitflags;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+bitflags! {
+    /// Bitwise combination of hit sounds for a hit object.
+    ///
+    /// Serializes to and from the same `u8` bitmask the .qua format stores, so existing
+    /// files round-trip unchanged.
+    #[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub struct HitSounds: u8 {
+        const NORMAL = 1;
+        const WHISTLE = 1 << 1;
+        const FINISH = 1 << 2;
+        const CLAP = 1 << 3;
+    }
+}
+
+impl From<u8> for HitSounds {
+    fn from(bits: u8) -> Self {
+        HitSounds::from_bits_truncate(bits)
+    }
+}
+
+impl From<HitSounds> for u8 {
+    fn from(sounds: HitSounds) -> Self {
+        sounds.bits()
+    }
+}
+
+impl Serialize for HitSounds {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.bits().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HitSounds {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bits = u8::deserialize(deserializer)?;
+        Ok(HitSounds::from_bits_truncate(bits))
+    }
+}
@@ -20,6 +20,14 @@ use serde::{Deserialize, Serialize};
 use std::{fmt::Display, fs::File, path::Path, str::FromStr};
 use serde_repr::*;
 
+pub mod analysis;
+pub mod builder;
+pub mod convert;
+pub mod hitsounds;
+pub mod mapset;
+
+pub use hitsounds::HitSounds;
+
 /// Error while parsing a qua file
 #[derive(Debug)]
 pub enum QuaError {
@@ -29,9 +37,8 @@ pub enum QuaError {
 
 /// Represents the .qua file format
 ///
-/// Hitsounds are not considered for now.
 /// Genre is unused, but does exist in the format.
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 #[serde(default)]
 pub struct Qua {
@@ -144,6 +151,136 @@ impl Qua {
     }
 }
 
+impl Qua {
+    /// Compute the normalized form of `slider_velocities` without modifying this `Qua`.
+    ///
+    /// In normalized form, the stored multiplier *is* the effective scroll speed, no longer
+    /// scaled by BPM. See [`Qua::normalize_svs`] for the conversion this performs in place.
+    pub fn with_normalized_svs(&self) -> Vec<ScrollVelocityInfo> {
+        self.rescale_svs(true)
+    }
+
+    /// Compute the denormalized form of `slider_velocities` without modifying this `Qua`.
+    ///
+    /// In denormalized form, the effective scroll speed at a time is `multiplier * (bpm / baseBpm)`.
+    /// See [`Qua::denormalize_svs`] for the conversion this performs in place.
+    pub fn with_denormalized_svs(&self) -> Vec<ScrollVelocityInfo> {
+        self.rescale_svs(false)
+    }
+
+    /// Replace `slider_velocities` with their normalized form and set
+    /// `bpm_does_not_affect_scroll_velocity` to true.
+    pub fn normalize_svs(&mut self) {
+        self.slider_velocities = self.with_normalized_svs();
+        self.bpm_does_not_affect_scroll_velocity = true;
+    }
+
+    /// Replace `slider_velocities` with their denormalized form and set
+    /// `bpm_does_not_affect_scroll_velocity` to false.
+    pub fn denormalize_svs(&mut self) {
+        self.slider_velocities = self.with_denormalized_svs();
+        self.bpm_does_not_affect_scroll_velocity = false;
+    }
+
+    /// The BPM of the timing point whose section covers the most total time.
+    ///
+    /// Ties are broken in favor of the higher BPM. Returns 0 if there are no timing points.
+    pub(crate) fn base_bpm(&self) -> f32 {
+        if self.timing_points.is_empty() {
+            return 0.0;
+        }
+        if self.timing_points.len() == 1 {
+            return self.timing_points[0].bpm;
+        }
+
+        let end_time = self.map_end_time();
+        let mut sorted = self.timing_points.clone();
+        sorted.sort_by(|a, b| a.start_time.partial_cmp(&b.start_time).unwrap());
+
+        let mut base_bpm = sorted[0].bpm;
+        let mut longest_duration = -1.0f32;
+        for (i, tp) in sorted.iter().enumerate() {
+            let next_start = sorted
+                .get(i + 1)
+                .map(|next| next.start_time)
+                .unwrap_or_else(|| end_time.max(tp.start_time));
+            let duration = next_start - tp.start_time;
+
+            if duration > longest_duration || (duration == longest_duration && tp.bpm > base_bpm) {
+                longest_duration = duration;
+                base_bpm = tp.bpm;
+            }
+        }
+
+        base_bpm
+    }
+
+    /// The time in milliseconds of the last hit object, used as the end of the final timing section
+    pub(crate) fn map_end_time(&self) -> f32 {
+        self.hit_objects
+            .iter()
+            .map(|h| h.end_time.max(h.start_time) as f32)
+            .fold(0.0, f32::max)
+    }
+
+    fn rescale_svs(&self, normalize: bool) -> Vec<ScrollVelocityInfo> {
+        let base_bpm = self.base_bpm();
+        if base_bpm == 0.0 {
+            return self.slider_velocities.clone();
+        }
+
+        enum Event {
+            Bpm(f32),
+            Sv(f32),
+        }
+
+        let mut timeline: Vec<(f32, Event)> = self
+            .timing_points
+            .iter()
+            .map(|tp| (tp.start_time, Event::Bpm(tp.bpm)))
+            .collect();
+        timeline.extend(
+            self.slider_velocities
+                .iter()
+                .map(|sv| (sv.start_time as f32, Event::Sv(sv.multiplier))),
+        );
+        timeline.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        // SVs before the first timing point use the first timing point's BPM
+        let mut current_bpm = self.timing_points.first().map(|tp| tp.bpm).unwrap_or(0.0);
+        let mut current_multiplier = self.initial_scroll_velocity;
+        let mut result = Vec::with_capacity(timeline.len());
+
+        let mut i = 0;
+        while i < timeline.len() {
+            let time = timeline[i].0;
+
+            // Apply every event at this timestamp before emitting, so a BPM change and an
+            // SV change landing on the same time produce a single entry instead of two.
+            while i < timeline.len() && timeline[i].0 == time {
+                match timeline[i].1 {
+                    Event::Bpm(bpm) => current_bpm = bpm,
+                    Event::Sv(multiplier) => current_multiplier = multiplier,
+                }
+                i += 1;
+            }
+
+            let multiplier = if normalize {
+                current_multiplier * current_bpm / base_bpm
+            } else {
+                current_multiplier * base_bpm / current_bpm
+            };
+
+            result.push(ScrollVelocityInfo {
+                start_time: time as i32,
+                multiplier,
+            });
+        }
+
+        result
+    }
+}
+
 impl FromStr for Qua {
     type Err = QuaError;
 
@@ -192,7 +329,7 @@ impl Default for Qua {
 }
 
 /// Game mode of the map
-#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum GameMode {
     Keys4 = 1,
     Keys7 = 2,
@@ -218,7 +355,7 @@ impl GameMode {
 /// Editor layers to separate notes into different layers.
 ///
 /// Color is provided in rrr,ggg,bbb format.
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 #[serde(default)]
 pub struct EditorLayerInfo {
@@ -241,7 +378,7 @@ impl Default for EditorLayerInfo {
 }
 
 /// Custom audio samples that can be assigned to different hit objects
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 #[serde(default)]
 pub struct CustomAudioSampleInfo {
@@ -261,7 +398,7 @@ impl Default for CustomAudioSampleInfo {
 }
 
 /// Sound effect played at a specific moment in time
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 #[serde(default)]
 pub struct SoundEffectInfo {
@@ -288,7 +425,7 @@ impl Default for SoundEffectInfo {
 /// If bpm_does_not_affect_scroll_velocity is true, then
 /// the BPM will scale the scroll velocity of the map in relation to its base BPM.
 /// If there is an existing scroll velocity, then it will be overridden.
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 #[serde(default)]
 pub struct TimingPointInfo {
@@ -316,7 +453,7 @@ impl Default for TimingPointInfo {
 /// A moment in time where the scroll velocity changes
 ///
 /// Will be overridden by following timing points
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct ScrollVelocityInfo {
     /// The time in milliseconds when the new SliderVelocity section begins
@@ -325,8 +462,17 @@ pub struct ScrollVelocityInfo {
     pub multiplier: f32,
 }
 
+impl Default for ScrollVelocityInfo {
+    fn default() -> Self {
+        Self {
+            start_time: 0,
+            multiplier: 1.0,
+        }
+    }
+}
+
 /// Time signature of the song
-#[derive(Serialize_repr, Deserialize_repr, Clone, PartialEq)]
+#[derive(Debug, Serialize_repr, Deserialize_repr, Clone, PartialEq)]
 #[repr(u8)]
 pub enum TimeSignature {
     Quadruple = 4,
@@ -336,7 +482,7 @@ pub enum TimeSignature {
 /// A note to be played in-game
 ///
 /// A long note will have an end_time > 0.
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 #[serde(default)]
 pub struct HitObjectInfo {
@@ -347,8 +493,7 @@ pub struct HitObjectInfo {
     /// The endtime of the HitObject (if greater than 0, it's considered a hold note.)
     pub end_time: i32,
     /// Bitwise combination of hit sounds for this object
-    // TODO: Handle hitsound bitflags
-    pub hit_sound: u8,
+    pub hit_sound: HitSounds,
     /// Key sounds to play when this object is hit.
     pub key_sounds: Vec<KeySoundInfo>,
     /// The layer in the editor that the object belongs to (index in the array).
@@ -361,7 +506,7 @@ impl Default for HitObjectInfo {
             start_time: 0,
             lane: 1,
             end_time: 0,
-            hit_sound: 0,
+            hit_sound: HitSounds::empty(),
             key_sounds: Vec::new(),
             editor_layer: 0,
         }
@@ -369,7 +514,7 @@ impl Default for HitObjectInfo {
 }
 
 /// Key sounds that are played for a specific note with a given volume
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 #[serde(default)]
 pub struct KeySoundInfo {
@@ -0,0 +1,106 @@
+//! Read and write `.qp` Quaver mapset archives: zip containers bundling several `.qua`
+//! difficulties together with their shared audio/background/banner assets.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Seek, Write},
+    path::Path,
+};
+
+use zip::{write::FileOptions, ZipArchive, ZipWriter};
+
+use crate::{Qua, QuaError};
+
+/// Error while reading or writing a `.qp` mapset archive
+#[derive(Debug)]
+pub enum MapsetError {
+    IoError(std::io::Error),
+    ZipError(zip::result::ZipError),
+    QuaError(QuaError),
+}
+
+impl From<std::io::Error> for MapsetError {
+    fn from(e: std::io::Error) -> Self {
+        MapsetError::IoError(e)
+    }
+}
+
+impl From<zip::result::ZipError> for MapsetError {
+    fn from(e: zip::result::ZipError) -> Self {
+        MapsetError::ZipError(e)
+    }
+}
+
+impl From<QuaError> for MapsetError {
+    fn from(e: QuaError) -> Self {
+        MapsetError::QuaError(e)
+    }
+}
+
+/// A Quaver mapset: every difficulty [`Qua`] it contains, plus the asset bytes referenced by
+/// their `audio_file`, `background_file`, and `banner_file` names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mapset {
+    /// The parsed difficulties contained in this mapset
+    pub quas: Vec<Qua>,
+    /// Asset bytes (audio, background, banner, ...), keyed by the file name the `.qua`
+    /// difficulties reference them by
+    pub assets: HashMap<String, Vec<u8>>,
+}
+
+impl Mapset {
+    /// Read a `.qp` mapset archive from a file
+    pub fn from_qp<P: AsRef<Path>>(path: P) -> Result<Mapset, MapsetError> {
+        let file = File::open(path)?;
+        Mapset::from_reader(file)
+    }
+
+    /// Read a `.qp` mapset archive from a reader
+    pub fn from_reader<R: Read + Seek>(reader: R) -> Result<Mapset, MapsetError> {
+        let mut archive = ZipArchive::new(reader)?;
+        let mut quas = Vec::new();
+        let mut assets = HashMap::new();
+
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let name = entry.name().to_string();
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes)?;
+
+            if name.ends_with(".qua") {
+                quas.push(Qua::from_reader(bytes.as_slice())?);
+            } else {
+                assets.insert(name, bytes);
+            }
+        }
+
+        Ok(Mapset { quas, assets })
+    }
+
+    /// Write this mapset back out as a `.qp` archive
+    pub fn to_qp<W: Write + Seek>(&self, writer: W) -> Result<(), MapsetError> {
+        let mut zip = ZipWriter::new(writer);
+        let options = FileOptions::default();
+
+        for (i, qua) in self.quas.iter().enumerate() {
+            // Real mapsets name each .qua after its map id; fall back to the index for
+            // unsubmitted maps, which default map_id to -1.
+            let name = if qua.map_id > 0 {
+                format!("{}.qua", qua.map_id)
+            } else {
+                format!("{}.qua", i)
+            };
+            zip.start_file(name, options)?;
+            qua.to_writer(&mut zip)?;
+        }
+
+        for (name, bytes) in &self.assets {
+            zip.start_file(name, options)?;
+            zip.write_all(bytes)?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
+}
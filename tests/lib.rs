@@ -53,4 +53,67 @@ mod tests {
 
         fs::remove_file(&new_path).expect("Could not remove file");
     }
+
+    #[test]
+    fn test_sv_normalize_denormalize_round_trip() {
+        let mut qua = Qua {
+            timing_points: vec![
+                TimingPointInfo {
+                    start_time: 0.0,
+                    bpm: 120.0,
+                    ..Default::default()
+                },
+                TimingPointInfo {
+                    start_time: 1000.0,
+                    bpm: 240.0,
+                    ..Default::default()
+                },
+            ],
+            slider_velocities: vec![ScrollVelocityInfo {
+                start_time: 500,
+                multiplier: 2.0,
+            }],
+            initial_scroll_velocity: 1.0,
+            ..Default::default()
+        };
+
+        qua.normalize_svs();
+        assert!(qua.bpm_does_not_affect_scroll_velocity);
+
+        let denormalized = qua.with_denormalized_svs();
+        let multiplier_at_500 = denormalized
+            .iter()
+            .rev()
+            .find(|sv| sv.start_time <= 500)
+            .expect("an SV covering t=500")
+            .multiplier;
+
+        assert!((multiplier_at_500 - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_dominant_bpm_picks_longest_section() {
+        let qua = Qua {
+            timing_points: vec![
+                TimingPointInfo {
+                    start_time: 0.0,
+                    bpm: 100.0,
+                    ..Default::default()
+                },
+                TimingPointInfo {
+                    start_time: 100.0,
+                    bpm: 200.0,
+                    ..Default::default()
+                },
+            ],
+            hit_objects: vec![HitObjectInfo {
+                start_time: 5000,
+                lane: 1,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(200.0, qua.analyze().dominant_bpm);
+    }
 }